@@ -0,0 +1,42 @@
+//! A public API for more fine-grained customization of bindgen's codegen.
+
+/// The kind of item a [`DeriveInfo`] is being reported for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeriveTypeKind {
+    /// A struct.
+    Struct,
+    /// A union.
+    Union,
+    /// An enum.
+    Enum,
+}
+
+/// Information about the type a [`ParseCallbacks::add_derives`] call is
+/// being asked to contribute extra derives for.
+#[derive(Debug)]
+pub struct DeriveInfo<'a> {
+    /// The canonical name of the type, as bindgen would emit it.
+    pub name: &'a str,
+    /// Whether `name` refers to a struct, union or enum.
+    pub kind: DeriveTypeKind,
+}
+
+/// A trait to allow configuring various aspects of the generated bindings.
+pub trait ParseCallbacks: ::std::fmt::Debug {
+    /// This function will be run on every named item, allowing changing
+    /// the names that end up being generated.
+    fn item_name(&self, _original_item_name: &str) -> Option<String> {
+        None
+    }
+
+    /// Allows to add extra derive attributes to `info`'s type, on top of the
+    /// ones bindgen computes automatically.
+    ///
+    /// The returned names are inserted into the same `#[derive(...)]` list
+    /// bindgen already builds for the type, so ordering and de-duplication
+    /// against bindgen's own derives are handled for you. Returning an empty
+    /// `Vec` leaves the generated output unchanged.
+    fn add_derives(&self, _info: &DeriveInfo) -> Vec<String> {
+        vec![]
+    }
+}
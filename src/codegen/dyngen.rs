@@ -0,0 +1,128 @@
+use quote;
+
+/// Accumulates the pieces needed to emit a dynamic-loading wrapper struct
+/// for a shared library: one field per bound function (as a function
+/// pointer, or `Option<fn pointer>` for symbols marked optional), a field
+/// holding the loaded `libloading::Library`, and inherent methods that
+/// forward through the stored pointers.
+///
+/// Functions are added one at a time via `push` as codegen walks the
+/// whitelisted functions, then `get_tokens` emits the final `struct` and its
+/// `impl` block.
+#[derive(Default)]
+pub struct DynamicItems {
+    /// `field_name: field_type` for every bound function.
+    struct_members: Vec<quote::Tokens>,
+    /// Statements run inside `new` that resolve each symbol into a local
+    /// binding matching its field name.
+    constructor_inits: Vec<quote::Tokens>,
+    /// `field_name: field_name` struct literal initializers, in the same
+    /// order as `struct_members`.
+    init_fields: Vec<quote::Tokens>,
+    /// Inherent methods forwarding to each stored function pointer.
+    methods: Vec<quote::Tokens>,
+}
+
+impl DynamicItems {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register one C function with the wrapper being built.
+    ///
+    /// `fn_ty` must already be the correctly-ABI'd function pointer type for
+    /// this function (i.e. lowered from its `FunctionSig`/`ClangAbi` the same
+    /// way the plain `extern` binding would be), so callers are expected to
+    /// build it via the existing `FunctionSig` lowering rather than passing
+    /// the ABI through here to be reconstructed a second time.
+    ///
+    /// Variadic functions can't be forwarded through a method with a fixed
+    /// signature, so only their raw function pointer field (an `Option` when
+    /// `is_optional` is set) is exposed; calling it is left `unsafe` and up
+    /// to the caller.
+    pub fn push(
+        &mut self,
+        ident: quote::Ident,
+        is_variadic: bool,
+        is_optional: bool,
+        fn_ty: quote::Tokens,
+        ret_ty: quote::Tokens,
+        arg_idents: &[quote::Tokens],
+        args: &[quote::Tokens],
+    ) {
+        let field_name = ident.clone();
+        // A NUL-terminated byte string literal, e.g. `b"foo\0"`, as
+        // `libloading::Library::get` expects.
+        let symbol = quote::Ident::new(format!("b\"{}\\0\"", field_name.as_ref()));
+
+        let field_type = if is_optional {
+            quote! { Option<#fn_ty> }
+        } else {
+            fn_ty.clone()
+        };
+        self.struct_members.push(quote! {
+            pub #field_name : #field_type
+        });
+        self.init_fields.push(quote! { #field_name });
+
+        if is_optional {
+            self.constructor_inits.push(quote! {
+                let #field_name = library.get(#symbol).map(|sym| *sym).ok();
+            });
+        } else {
+            self.constructor_inits.push(quote! {
+                let #field_name = *(library.get(#symbol)?);
+            });
+        }
+
+        if !is_variadic {
+            let call = if is_optional {
+                quote! {
+                    (self.#field_name.expect("symbol was not loaded"))(#(#arg_idents),*)
+                }
+            } else {
+                quote! {
+                    (self.#field_name)(#(#arg_idents),*)
+                }
+            };
+
+            self.methods.push(quote! {
+                pub unsafe fn #field_name (&self, #(#args),*) -> #ret_ty {
+                    #call
+                }
+            });
+        }
+    }
+
+    /// Emit the final `struct #lib_ident { .. }` definition and its `impl`
+    /// block, including the `new` constructor.
+    pub fn get_tokens(self, lib_ident: quote::Ident) -> quote::Tokens {
+        let struct_members = &self.struct_members;
+        let constructor_inits = &self.constructor_inits;
+        let init_fields = &self.init_fields;
+        let methods = &self.methods;
+
+        quote! {
+            pub struct #lib_ident {
+                __library: ::libloading::Library,
+                #(#struct_members),*
+            }
+
+            impl #lib_ident {
+                pub unsafe fn new<P>(path: P) -> Result<Self, ::libloading::Error>
+                where
+                    P: AsRef<::std::ffi::OsStr>,
+                {
+                    let library = ::libloading::Library::new(path)?;
+                    #(#constructor_inits)*
+                    Ok(#lib_ident {
+                        __library: library,
+                        #(#init_fields),*
+                    })
+                }
+
+                #(#methods)*
+            }
+        }
+    }
+}
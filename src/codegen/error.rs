@@ -0,0 +1,42 @@
+use std::error;
+use std::fmt;
+
+/// Errors that can occur during codegen.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CodegenError {
+    /// An error occurred while serializing a static-inline function wrapper.
+    Serialize {
+        /// A description of the issue.
+        msg: String,
+        /// The location of the issue.
+        loc: String,
+    },
+    /// An error occurred while writing the generated wrapper source file.
+    Io(String),
+}
+
+impl fmt::Display for CodegenError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            CodegenError::Serialize { ref msg, ref loc } => {
+                write!(f, "serialization error at {}: {}", loc, msg)
+            }
+            CodegenError::Io(ref err) => err.fmt(f),
+        }
+    }
+}
+
+impl error::Error for CodegenError {
+    fn description(&self) -> &str {
+        match *self {
+            CodegenError::Serialize { .. } => "serialization error",
+            CodegenError::Io(..) => "io error",
+        }
+    }
+}
+
+impl From<::std::io::Error> for CodegenError {
+    fn from(err: ::std::io::Error) -> Self {
+        CodegenError::Io(err.to_string())
+    }
+}
@@ -0,0 +1,196 @@
+use ir::comp::{BitfieldUnit, CompInfo, CompKind, Field, FieldData, FieldMethods};
+use ir::context::BindgenContext;
+use ir::derive::CanTriviallyDerivePartialEq;
+use ir::item::{HasTypeParamInArray, IsOpaque, Item};
+use ir::ty::{RUST_DERIVE_IN_ARRAY_LIMIT, TypeKind};
+use quote;
+
+/// Generate a manual `impl PartialEq` for a struct or union that can't
+/// `#[derive(PartialEq)]`, mirroring `gen_debug_impl`.
+///
+/// Returns `None` when one of the fields has a type we can't compare (e.g. an
+/// unconstrained generic type parameter), in which case the caller should
+/// leave the type without a generated `PartialEq` impl at all.
+pub fn gen_partialeq_impl(
+    ctx: &BindgenContext,
+    comp_info: &CompInfo,
+    item: &Item,
+    fields: &[Field],
+) -> Option<quote::Tokens> {
+    let mut tokens = vec![];
+
+    if item.is_opaque(ctx, &()) {
+        tokens.push(quote! {
+            &self._bindgen_opaque_blob[..] == &other._bindgen_opaque_blob[..]
+        });
+    } else {
+        match comp_info.kind() {
+            CompKind::Union => {
+                tokens.push(quote! {
+                    &self.bindgen_union_field[..] == &other.bindgen_union_field[..]
+                });
+            }
+            CompKind::Struct => {
+                for field in fields {
+                    match *field {
+                        Field::DataMember(ref fd) => match fd.impl_partialeq(ctx, ()) {
+                            Some(toks) => tokens.push(toks),
+                            // Bail out entirely: we can't claim equality
+                            // while silently ignoring a field we can't
+                            // compare.
+                            None => return None,
+                        },
+                        // A bitfield unit made up entirely of unnamed
+                        // (padding) bitfields has nothing to compare; skip
+                        // it rather than bailing out or splicing an empty
+                        // operand into the `&&` chain below.
+                        Field::Bitfields(ref bu) => {
+                            if let Some(toks) = bu.impl_partialeq(ctx, ()) {
+                                tokens.push(toks);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    if tokens.is_empty() {
+        // Every field was skipped (e.g. the struct consists entirely of
+        // unnamed padding bitfields); there's nothing to compare, so the
+        // types are trivially equal.
+        tokens.push(quote! { true });
+    }
+
+    Some(quote! {
+        fn eq(&self, other: &Self) -> bool {
+            #( #tokens )&&*
+        }
+    })
+}
+
+/// A trait for the things which we can codegen tokens that contribute towards
+/// a generated `impl PartialEq`.
+pub trait ImplPartialEq<'a> {
+    /// Any extra parameter required by this particular `ImplPartialEq`
+    /// implementation.
+    type Extra;
+
+    /// Generate the boolean expression comparing `self` and `other` for this
+    /// field, or `None` if the field's type can't be compared (for a
+    /// `BitfieldUnit`: if it has no named bitfields to compare).
+    fn impl_partialeq(
+        &self,
+        ctx: &BindgenContext,
+        extra: Self::Extra,
+    ) -> Option<quote::Tokens>;
+}
+
+impl<'a> ImplPartialEq<'a> for FieldData {
+    type Extra = ();
+
+    fn impl_partialeq(
+        &self,
+        ctx: &BindgenContext,
+        _: Self::Extra,
+    ) -> Option<quote::Tokens> {
+        if let Some(name) = self.name() {
+            ctx.resolve_item(self.ty()).impl_partialeq(ctx, name)
+        } else {
+            None
+        }
+    }
+}
+
+impl<'a> ImplPartialEq<'a> for BitfieldUnit {
+    type Extra = ();
+
+    fn impl_partialeq(
+        &self,
+        ctx: &BindgenContext,
+        _: Self::Extra,
+    ) -> Option<quote::Tokens> {
+        let mut tokens = vec![];
+        for bu in self.bitfields() {
+            if let Some(name) = bu.name() {
+                let name_ident = ctx.rust_ident_raw(name);
+                tokens.push(quote! {
+                    self.#name_ident () == other.#name_ident ()
+                });
+            }
+        }
+
+        if tokens.is_empty() {
+            // Every bitfield in this unit is unnamed padding; there's
+            // nothing to compare.
+            return None;
+        }
+
+        Some(quote! { #( #tokens )&&* })
+    }
+}
+
+impl<'a> ImplPartialEq<'a> for Item {
+    type Extra = &'a str;
+
+    fn impl_partialeq(
+        &self,
+        ctx: &BindgenContext,
+        name: &str,
+    ) -> Option<quote::Tokens> {
+        let name_ident = ctx.rust_ident_raw(name);
+
+        // We don't know if blacklisted items `impl PartialEq` or not, so we
+        // can't compare against them either.
+        if !ctx.whitelisted_items().contains(&self.id()) {
+            return None;
+        }
+
+        let ty = match self.as_type() {
+            Some(ty) => ty,
+            None => {
+                return None;
+            }
+        };
+
+        match *ty.kind() {
+            // The generic is not required to implement `PartialEq`, so we
+            // can't compare that field.
+            TypeKind::TypeParam => None,
+
+            TypeKind::Array(_, len) => {
+                if self.has_type_param_in_array(ctx) {
+                    None
+                } else if len < RUST_DERIVE_IN_ARRAY_LIMIT {
+                    // The simple case.
+                    Some(quote! { self.#name_ident == other.#name_ident })
+                } else {
+                    // Arrays longer than the derive limit can't `#[derive]`,
+                    // so compare them as slices instead.
+                    Some(quote! {
+                        &self.#name_ident [..] == &other.#name_ident [..]
+                    })
+                }
+            }
+
+            TypeKind::ResolvedTypeRef(t) |
+            TypeKind::TemplateAlias(t, _) |
+            TypeKind::Alias(t) => {
+                // We follow the aliases, same as `impl_debug` does.
+                ctx.resolve_item(t).impl_partialeq(ctx, name)
+            }
+
+            TypeKind::Comp(..) | TypeKind::TemplateInstantiation(..) => {
+                if ty.can_trivially_derive_partialeq() {
+                    Some(quote! { self.#name_ident == other.#name_ident })
+                } else {
+                    None
+                }
+            }
+
+            TypeKind::Opaque => None,
+
+            _ => Some(quote! { self.#name_ident == other.#name_ident }),
+        }
+    }
+}
@@ -0,0 +1,48 @@
+use proc_macro2;
+use quote::ToTokens;
+use syn;
+
+/// Coalesce consecutive `extern` blocks that share the same ABI and
+/// attributes into a single block, to avoid bindgen's one-`extern`-block-per
+/// function/static output.
+///
+/// Only runs of foreign-mod items that are immediately adjacent (no other
+/// item between them) and have an identical ABI string and attribute list
+/// are merged; everything else, including the relative order of all other
+/// top-level items, is left untouched.
+pub fn merge_extern_blocks(
+    items: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let file = match syn::parse2::<syn::File>(items.clone()) {
+        Ok(file) => file,
+        // If for whatever reason we can't parse our own generated output,
+        // fall back to emitting it unmodified rather than failing outright.
+        Err(_) => return items,
+    };
+
+    let mut new_items: Vec<syn::Item> = vec![];
+
+    for item in file.items {
+        if let syn::Item::ForeignMod(ref foreign_mod) = item {
+            if let Some(syn::Item::ForeignMod(ref mut last)) = new_items.last_mut() {
+                if can_be_merged(last, foreign_mod) {
+                    last.items.extend(foreign_mod.items.clone());
+                    continue;
+                }
+            }
+        }
+
+        new_items.push(item);
+    }
+
+    let file = syn::File {
+        items: new_items,
+        ..file
+    };
+
+    file.into_token_stream()
+}
+
+fn can_be_merged(a: &syn::ItemForeignMod, b: &syn::ItemForeignMod) -> bool {
+    a.abi == b.abi && a.attrs == b.attrs
+}
@@ -0,0 +1,33 @@
+//! Optional passes that run over the fully generated token stream before it
+//! is handed back to the caller, to make the output more readable or more
+//! stable without touching the core codegen logic above.
+
+mod merge_extern_blocks;
+mod sort_semantically;
+
+use self::merge_extern_blocks::merge_extern_blocks;
+use self::sort_semantically::sort_semantically;
+use ir::context::BindgenContext;
+use proc_macro2;
+
+/// Run every postprocessing pass enabled in `ctx`'s options over `items`, in
+/// a fixed order, and return the resulting token stream.
+///
+/// Each pass is a no-op when its corresponding option is disabled, so by
+/// default this function returns `items` unchanged.
+pub fn postprocessing(
+    ctx: &BindgenContext,
+    items: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let mut items = items;
+
+    if ctx.options().merge_extern_blocks {
+        items = merge_extern_blocks(items);
+    }
+
+    if ctx.options().sort_semantically {
+        items = sort_semantically(items);
+    }
+
+    items
+}
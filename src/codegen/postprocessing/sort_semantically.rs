@@ -0,0 +1,51 @@
+use proc_macro2;
+use quote::ToTokens;
+use syn;
+
+/// Reorder top-level items into a deterministic, human-friendly grouping,
+/// independent of the order clang happened to traverse the AST in.
+///
+/// Items are grouped by kind in the order below, and keep their original
+/// relative order within each group:
+///
+/// 1. `mod` declarations
+/// 2. type aliases (`type Foo = ...`)
+/// 3. structs, unions and enums
+/// 4. constants and statics
+/// 5. `extern` blocks
+/// 6. inherent `impl` blocks
+/// 7. trait `impl` blocks
+/// 8. everything else
+pub fn sort_semantically(
+    items: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let file = match syn::parse2::<syn::File>(items.clone()) {
+        Ok(file) => file,
+        // If for whatever reason we can't parse our own generated output,
+        // fall back to emitting it unmodified rather than failing outright.
+        Err(_) => return items,
+    };
+
+    let mut items: Vec<_> = file.items.into_iter().enumerate().collect();
+    items.sort_by_key(|&(index, ref item)| (item_group(item), index));
+
+    let file = syn::File {
+        items: items.into_iter().map(|(_, item)| item).collect(),
+        ..file
+    };
+
+    file.into_token_stream()
+}
+
+fn item_group(item: &syn::Item) -> u8 {
+    match *item {
+        syn::Item::Mod(..) => 0,
+        syn::Item::Type(..) => 1,
+        syn::Item::Struct(..) | syn::Item::Union(..) | syn::Item::Enum(..) => 2,
+        syn::Item::Const(..) | syn::Item::Static(..) => 3,
+        syn::Item::ForeignMod(..) => 4,
+        syn::Item::Impl(ref item) if item.trait_.is_none() => 5,
+        syn::Item::Impl(..) => 6,
+        _ => 7,
+    }
+}
@@ -0,0 +1,194 @@
+use super::error::CodegenError;
+use ir::context::BindgenContext;
+use ir::function::{Function, FunctionSig};
+use ir::item::{Item, ItemCanonicalName};
+use ir::ty::{FloatKind, IntKind, TypeId, TypeKind};
+use std::io::Write;
+
+/// A wrapper name is deterministically derived from the original symbol so
+/// re-running bindgen over the same header yields the same `.c` file.
+fn wrapper_name(name: &str) -> String {
+    format!("{}__extern", name)
+}
+
+/// Types that know how to serialize themselves into the generated C wrapper
+/// source, analogous to how `ToRustTy`/codegen traits let IR nodes describe
+/// themselves for the Rust side.
+pub trait CSerialize<'a> {
+    /// Extra context required to serialize this node.
+    type Extra;
+
+    /// Write this node's C source representation to `writer`.
+    fn serialize<W: Write>(
+        &self,
+        ctx: &BindgenContext,
+        extra: Self::Extra,
+        writer: &mut W,
+    ) -> Result<(), CodegenError>;
+}
+
+impl<'a> CSerialize<'a> for Function {
+    type Extra = &'a Item;
+
+    fn serialize<W: Write>(
+        &self,
+        ctx: &BindgenContext,
+        item: &Item,
+        writer: &mut W,
+    ) -> Result<(), CodegenError> {
+        let signature = match ctx.resolve_type(self.signature()).kind() {
+            &TypeKind::Function(ref sig) => sig,
+            _ => {
+                return Err(CodegenError::Serialize {
+                    msg: "Function signature is not a function type".to_owned(),
+                    loc: item.location().map_or_else(String::new, |l| format!("{}", l)),
+                })
+            }
+        };
+
+        if signature.is_variadic() {
+            // Variadic inline functions can't be forwarded portably; skip
+            // them with a diagnostic instead of emitting broken C.
+            warn!(
+                "Skipping the wrapper for variadic static function {:?}",
+                self.name()
+            );
+            return Ok(());
+        }
+
+        let name = self.name();
+        let wrapper = wrapper_name(name);
+        let is_void_return = match *ctx.resolve_type(signature.return_type()).kind() {
+            TypeKind::Void => true,
+            _ => false,
+        };
+
+        serialize_sig(signature, ctx, &wrapper, writer)?;
+        writeln!(writer, " {{").map_err(CodegenError::from)?;
+
+        let args: Vec<String> = signature
+            .argument_types()
+            .iter()
+            .enumerate()
+            .map(|(i, &(ref arg_name, _))| {
+                arg_name.clone().unwrap_or_else(|| format!("arg{}", i))
+            })
+            .collect();
+
+        if is_void_return {
+            writeln!(writer, "  {}({});", name, args.join(", "))
+        } else {
+            writeln!(writer, "  return {}({});", name, args.join(", "))
+        }
+        .map_err(CodegenError::from)?;
+
+        writeln!(writer, "}}").map_err(CodegenError::from)?;
+
+        Ok(())
+    }
+}
+
+fn serialize_sig<W: Write>(
+    signature: &FunctionSig,
+    ctx: &BindgenContext,
+    name: &str,
+    writer: &mut W,
+) -> Result<(), CodegenError> {
+    let ret = serialize_type(ctx, signature.return_type());
+    let args: Vec<String> = signature
+        .argument_types()
+        .iter()
+        .enumerate()
+        .map(|(i, &(ref arg_name, ty))| {
+            let arg_name = arg_name.clone().unwrap_or_else(|| format!("arg{}", i));
+            format!("{} {}", serialize_type(ctx, ty), arg_name)
+        })
+        .collect();
+
+    write!(writer, "{} {}({})", ret, name, args.join(", "))
+        .map_err(CodegenError::from)
+}
+
+/// Render `ty_id`'s C spelling, e.g. `struct Foo*`, `unsigned long`, `void`.
+///
+/// This only needs to cover the shapes that can legally appear as a C
+/// function's parameter or return type.
+fn serialize_type(ctx: &BindgenContext, ty_id: TypeId) -> String {
+    let ty = ctx.resolve_type(ty_id);
+
+    match *ty.kind() {
+        TypeKind::Void => "void".to_owned(),
+        TypeKind::NullPtr => "void*".to_owned(),
+
+        TypeKind::Int(kind) => int_kind_c_name(kind).to_owned(),
+        TypeKind::Float(kind) => float_kind_c_name(kind).to_owned(),
+        TypeKind::Complex(kind) => format!("{} _Complex", float_kind_c_name(kind)),
+
+        TypeKind::Pointer(inner) => {
+            match *ctx.resolve_type(inner).canonical_type(ctx).kind() {
+                TypeKind::Function(..) => "void*".to_owned(),
+                _ => format!("{}*", serialize_type(ctx, inner)),
+            }
+        }
+
+        TypeKind::ResolvedTypeRef(t) |
+        TypeKind::Alias(t) |
+        TypeKind::TemplateAlias(t, _) => serialize_type(ctx, t),
+
+        TypeKind::Comp(ref info) => {
+            let keyword = match info.kind() {
+                ::ir::comp::CompKind::Union => "union",
+                ::ir::comp::CompKind::Struct => "struct",
+            };
+            format!(
+                "{} {}",
+                keyword,
+                ctx.resolve_item(ty_id).canonical_name(ctx)
+            )
+        }
+
+        TypeKind::Enum(..) => {
+            format!("enum {}", ctx.resolve_item(ty_id).canonical_name(ctx))
+        }
+
+        // Anything else (arrays, function pointers, opaque blobs, ...)
+        // shouldn't appear as a parameter or return type of a function we
+        // agreed to wrap; fall back to `void*` rather than emit nonsense C.
+        _ => "void*".to_owned(),
+    }
+}
+
+fn int_kind_c_name(kind: IntKind) -> &'static str {
+    match kind {
+        IntKind::Bool => "bool",
+        IntKind::Char { .. } => "char",
+        IntKind::SChar => "signed char",
+        IntKind::UChar => "unsigned char",
+        IntKind::WChar => "wchar_t",
+        IntKind::Short => "short",
+        IntKind::UShort => "unsigned short",
+        IntKind::Int => "int",
+        IntKind::UInt => "unsigned int",
+        IntKind::Long => "long",
+        IntKind::ULong => "unsigned long",
+        IntKind::LongLong => "long long",
+        IntKind::ULongLong => "unsigned long long",
+        IntKind::I8 | IntKind::U8 => "char",
+        IntKind::I16 | IntKind::U16 => "short",
+        IntKind::I32 | IntKind::U32 => "int",
+        IntKind::I64 | IntKind::U64 => "long long",
+        IntKind::Custom { name, .. } => name,
+        // `__int128`/`u128` and any future variants: there's no portable C89
+        // spelling, so fall back to the closest integer type we do emit.
+        _ => "long long",
+    }
+}
+
+fn float_kind_c_name(kind: FloatKind) -> &'static str {
+    match kind {
+        FloatKind::Float => "float",
+        FloatKind::Double => "double",
+        FloatKind::LongDouble => "long double",
+        _ => "double",
+    }
+}